@@ -0,0 +1,43 @@
+use quote::quote;
+use syn::{parse_macro_input, visit_mut::VisitMut, Item};
+
+use crate::{block::MathBlock, visitor::MathBlockVisitor};
+
+/// Shared implementation behind the `#[checked_fn]`/`#[overflowing_fn]`/`#[saturating_fn]`/
+/// `#[propagating_fn]` attribute macros.
+///
+/// Accepts a `fn`, `impl`, or `mod` item and rewrites every arithmetic expression in its
+/// body using `B`, the same way the matching brace-macro does for a bare `Block`.
+pub(crate) fn expand_item<B: MathBlock>(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "this attribute does not take any arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let mut item = parse_macro_input!(item as Item);
+    match try_expand_item::<B>(&mut item) {
+        Ok(()) => quote! { #item }.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn try_expand_item<B: MathBlock>(item: &mut Item) -> syn::Result<()> {
+    let mut visitor = MathBlockVisitor::<B>::new();
+    match item {
+        Item::Fn(item_fn) => visitor.visit_item_fn_mut(item_fn),
+        Item::Impl(item_impl) => visitor.visit_item_impl_mut(item_impl),
+        Item::Mod(item_mod) => visitor.visit_item_mod_mut(item_mod),
+        other => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "expected a `fn`, `impl`, or `mod` item",
+            ))
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+use syn::{parse_quote, Expr};
+
+use crate::expr::Op;
+
+/// Describes how a particular overflow-handling mode rewrites arithmetic operators.
+///
+/// Each macro (`checked!`, `overflowing!`, `saturating!`, `propagating!`, ...) is
+/// backed by a unit struct implementing this trait, which [`crate::visitor::MathBlockVisitor`]
+/// consults to turn `a + b` into the right method call.
+pub(crate) trait MathBlock {
+    /// The method name that replaces `op`, e.g. `checked_add` for [`Op::Add`], or
+    /// `None` if this mode has no sensible method for `op` (e.g. there is no
+    /// `saturating_shl`), in which case the operator is left untouched.
+    fn method(op: Op) -> Option<&'static str>;
+
+    /// Wraps a method call such as `(a).checked_add(b)` into the final expression
+    /// for this mode, e.g. unwrapping the `Option`, propagating it with `?`, or
+    /// taking `.0` of an `(T, bool)` pair.
+    fn finish(call: Expr, op: Op) -> Expr;
+}
+
+/// Backs the [`crate::checked`] macro.
+pub(crate) struct Checked;
+
+/// Backs the [`crate::overflowing`] macro.
+pub(crate) struct Overflowing;
+
+/// Backs the [`crate::saturating`] macro.
+pub(crate) struct Saturating;
+
+/// Backs the [`crate::propagating`] macro.
+pub(crate) struct Propagating;
+
+impl MathBlock for Checked {
+    fn method(op: Op) -> Option<&'static str> {
+        Some(match op {
+            Op::Add => "checked_add",
+            Op::Sub => "checked_sub",
+            Op::Mul => "checked_mul",
+            Op::Div => "checked_div",
+            Op::Rem => "checked_rem",
+            Op::Neg => "checked_neg",
+            Op::Shl => "checked_shl",
+            Op::Shr => "checked_shr",
+        })
+    }
+
+    fn finish(call: Expr, op: Op) -> Expr {
+        let message = format!("attempt to {} with overflow", op.verb());
+        parse_quote! { (#call).expect(#message) }
+    }
+}
+
+impl MathBlock for Overflowing {
+    fn method(op: Op) -> Option<&'static str> {
+        Some(match op {
+            Op::Add => "overflowing_add",
+            Op::Sub => "overflowing_sub",
+            Op::Mul => "overflowing_mul",
+            Op::Div => "overflowing_div",
+            Op::Rem => "overflowing_rem",
+            Op::Neg => "overflowing_neg",
+            Op::Shl => "overflowing_shl",
+            Op::Shr => "overflowing_shr",
+        })
+    }
+
+    fn finish(call: Expr, _op: Op) -> Expr {
+        parse_quote! { (#call).0 }
+    }
+}
+
+impl MathBlock for Saturating {
+    fn method(op: Op) -> Option<&'static str> {
+        Some(match op {
+            Op::Add => "saturating_add",
+            Op::Sub => "saturating_sub",
+            Op::Mul => "saturating_mul",
+            // `saturating_div` still panics on a zero divisor, like `overflowing_div`
+            // does and like `/` does by default (`checked_div` returns `None` instead);
+            // saturation only kicks in for the `MIN / -1` overflow case. See
+            // `crate::saturating!`'s doc for the user-facing version of this note.
+            Op::Div => "saturating_div",
+            // There is no `saturating_rem` in std. `wrapping_rem` panics on a zero
+            // divisor the same way, and for `MIN % -1` returns the exact result, `0`.
+            Op::Rem => "wrapping_rem",
+            Op::Neg => "saturating_neg",
+            // There is no `saturating_shl`/`saturating_shr` in std: saturating a shift
+            // amount isn't a meaningful operation, so these are left untouched.
+            Op::Shl | Op::Shr => return None,
+        })
+    }
+
+    fn finish(call: Expr, _op: Op) -> Expr {
+        call
+    }
+}
+
+impl MathBlock for Propagating {
+    fn method(op: Op) -> Option<&'static str> {
+        Checked::method(op)
+    }
+
+    fn finish(call: Expr, _op: Op) -> Expr {
+        parse_quote! { (#call)? }
+    }
+}
+
+/// Backs the [`crate::strict`] macro.
+pub(crate) struct Strict;
+
+impl MathBlock for Strict {
+    fn method(op: Op) -> Option<&'static str> {
+        Some(match op {
+            Op::Add => "strict_add",
+            Op::Sub => "strict_sub",
+            Op::Mul => "strict_mul",
+            Op::Div => "strict_div",
+            Op::Rem => "strict_rem",
+            Op::Neg => "strict_neg",
+            Op::Shl => "strict_shl",
+            Op::Shr => "strict_shr",
+        })
+    }
+
+    fn finish(call: Expr, _op: Op) -> Expr {
+        call
+    }
+}
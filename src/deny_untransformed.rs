@@ -0,0 +1,87 @@
+use proc_macro2::{Spacing, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::{parse_macro_input, visit::Visit, Item, Macro};
+
+/// Implementation behind the `#[deny_untransformed]` attribute.
+///
+/// Scans a `fn`, `impl`, or `mod` item for arithmetic operators hidden inside another
+/// macro invocation's token stream — the one syntactic position `MathBlockVisitor`
+/// can't reach, since `syn` has no way to parse an arbitrary macro's arguments as
+/// expressions — and turns each into a compile error instead of letting it silently
+/// keep Rust's default overflow behavior. Stack it outside one of the mode attributes,
+/// e.g. `#[deny_untransformed] #[checked_fn]`, so it runs on the original source before
+/// that attribute rewrites anything.
+pub(crate) fn expand_item(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return syn::Error::new_spanned(attr, "this attribute does not take any arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let item2 = proc_macro2::TokenStream::from(item.clone());
+    let parsed = parse_macro_input!(item as Item);
+
+    let mut visitor = DenyUntransformedVisitor::default();
+    visitor.visit_item(&parsed);
+
+    match visitor.error {
+        Some(err) => err.to_compile_error().into(),
+        None => quote! { #item2 }.into(),
+    }
+}
+
+/// Walks a syntax tree looking for macro invocations, in any position syn represents
+/// them (`Expr::Macro`, `Stmt::Macro`, `Item::Macro`, ...), and records the first
+/// arithmetic-looking token found inside one, since an opaque macro invocation is the
+/// main syntactic position the mode visitors can't reach: `syn` has no way to parse the
+/// contents of an arbitrary macro call as expressions.
+#[derive(Default)]
+struct DenyUntransformedVisitor {
+    error: Option<syn::Error>,
+}
+
+impl<'ast> Visit<'ast> for DenyUntransformedVisitor {
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        if self.error.is_some() || mac.path.is_ident("default") {
+            return;
+        }
+
+        if let Some(span) = find_arithmetic_token(mac.tokens.clone()) {
+            self.error = Some(syn::Error::new(
+                span,
+                "arithmetic operator inside this macro invocation is not rewritten by \
+                 `overf`'s modes; move it outside the macro call, or wrap it manually, \
+                 to avoid silently keeping Rust's default overflow behavior",
+            ));
+        }
+    }
+}
+
+/// Recursively looks for a `+`, `-`, `*`, `/`, or `%` that isn't the first character of a
+/// multi-char token (like `->`, `+=`, `*=`), anywhere in `tokens`.
+///
+/// This is a token-level heuristic, not a parse: it can't tell a unary `*` dereference
+/// from a multiplication, so it may flag a dereference inside a macro call as well.
+fn find_arithmetic_token(tokens: TokenStream) -> Option<Span> {
+    for tt in tokens {
+        match tt {
+            TokenTree::Group(group) => {
+                if let Some(span) = find_arithmetic_token(group.stream()) {
+                    return Some(span);
+                }
+            }
+            TokenTree::Punct(punct) => {
+                let is_arithmetic = matches!(punct.as_char(), '+' | '-' | '*' | '/' | '%');
+                if is_arithmetic && punct.spacing() == Spacing::Alone {
+                    return Some(punct.span());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
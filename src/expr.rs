@@ -0,0 +1,122 @@
+use proc_macro2::Span;
+use syn::{parse_quote, BinOp, Expr, UnOp};
+
+use crate::block::MathBlock;
+
+/// The arithmetic operators this crate knows how to rewrite.
+///
+/// Kept distinct from [`syn::BinOp`]/[`syn::UnOp`] so that the rewrite logic
+/// doesn't have to match on operators (like `&&` or `==`) that no
+/// [`MathBlock`] mode touches, and so a compound assignment (`*=`) can share
+/// the same [`Op::Mul`] as its binary counterpart (`*`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Neg,
+    Shl,
+    Shr,
+}
+
+impl Op {
+    /// The verb used in panic/expect messages, mirroring rustc's own overflow
+    /// messages (e.g. `attempt to add with overflow`).
+    pub(crate) fn verb(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "subtract",
+            Op::Mul => "multiply",
+            Op::Div => "divide",
+            Op::Rem => "calculate the remainder",
+            Op::Neg => "negate",
+            Op::Shl => "shift left",
+            Op::Shr => "shift right",
+        }
+    }
+}
+
+/// Maps a [`syn::BinOp`] to the [`Op`] it corresponds to, or `None` if this
+/// crate doesn't rewrite it (e.g. `&&`, `==`, `|=`).
+pub(crate) fn bin_op(op: BinOp) -> Option<Op> {
+    match op {
+        BinOp::Add(_) => Some(Op::Add),
+        BinOp::Sub(_) => Some(Op::Sub),
+        BinOp::Mul(_) => Some(Op::Mul),
+        BinOp::Div(_) => Some(Op::Div),
+        BinOp::Rem(_) => Some(Op::Rem),
+        BinOp::Shl(_) => Some(Op::Shl),
+        BinOp::Shr(_) => Some(Op::Shr),
+        _ => None,
+    }
+}
+
+/// Maps a compound-assignment [`syn::BinOp`] (e.g. `*=`) to the [`Op`] it
+/// performs, or `None` if this crate doesn't rewrite it (e.g. `&=`).
+pub(crate) fn compound_assign_op(op: BinOp) -> Option<Op> {
+    match op {
+        BinOp::AddAssign(_) => Some(Op::Add),
+        BinOp::SubAssign(_) => Some(Op::Sub),
+        BinOp::MulAssign(_) => Some(Op::Mul),
+        BinOp::DivAssign(_) => Some(Op::Div),
+        BinOp::RemAssign(_) => Some(Op::Rem),
+        _ => None,
+    }
+}
+
+/// Maps a [`syn::UnOp`] to the [`Op`] it corresponds to, or `None` if this
+/// crate doesn't rewrite it (e.g. `!x`, `*x`).
+pub(crate) fn unary_op(op: UnOp) -> Option<Op> {
+    match op {
+        UnOp::Neg(_) => Some(Op::Neg),
+        _ => None,
+    }
+}
+
+/// Rewrites `left <op> right` into the method-call form appropriate for `B`,
+/// or returns `None` if `B` doesn't support rewriting `op` (e.g. there is no
+/// `saturating_shl`).
+///
+/// `<<`/`>>` accept a right-hand side of any integer type, but the `*_shl`/`*_shr`
+/// methods this rewrites them to all take `rhs: u32`, so the shift amount is cast to
+/// `u32` first; this matches the range `<<`/`>>` themselves actually support (a shift
+/// amount that doesn't fit in `u32` always overflows the type being shifted anyway).
+pub(crate) fn rewrite_binary<B: MathBlock>(op: Op, left: Expr, right: Expr) -> Option<Expr> {
+    let method = syn::Ident::new(B::method(op)?, Span::call_site());
+    let call: Expr = if matches!(op, Op::Shl | Op::Shr) {
+        parse_quote! { (#left).#method((#right) as u32) }
+    } else {
+        parse_quote! { (#left).#method(#right) }
+    };
+    Some(B::finish(call, op))
+}
+
+/// Rewrites `left <op>= right` into `*place = <rewritten *place op right>`, where
+/// `place` is a reference taken to `left` once, rather than splicing `left` into both
+/// the assignment target and the rewritten right-hand side: `left` may be an arbitrary
+/// place expression (e.g. `v[idx()]`), and evaluating it twice would run its
+/// side effects twice.
+///
+/// Returns `None` if `B` doesn't support rewriting `op`.
+pub(crate) fn rewrite_compound_assign<B: MathBlock>(
+    op: Op,
+    left: Expr,
+    right: Expr,
+) -> Option<Expr> {
+    let place: Expr = parse_quote! { (*__overf_place) };
+    let rhs = rewrite_binary::<B>(op, place.clone(), right)?;
+    Some(parse_quote! {{
+        let __overf_place = &mut (#left);
+        #place = #rhs;
+    }})
+}
+
+/// Rewrites `<op> expr` (currently only unary `-`) into the method-call form
+/// appropriate for `B`, or returns `None` if `B` doesn't support `op`.
+pub(crate) fn rewrite_unary<B: MathBlock>(op: Op, expr: Expr) -> Option<Expr> {
+    let method = syn::Ident::new(B::method(op)?, Span::call_site());
+    let call: Expr = parse_quote! { (#expr).#method() };
+    Some(B::finish(call, op))
+}
@@ -1,13 +1,17 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![doc = include_str!("../README.md")]
 
-use block::{Checked, MathBlock, Overflowing, Propagating, Saturating};
+use attr::expand_item;
+use block::{Checked, MathBlock, Overflowing, Propagating, Saturating, Strict};
 use quote::quote;
 use syn::{parse_macro_input, visit_mut::VisitMut, Block};
 use visitor::MathBlockVisitor;
 
+mod attr;
 mod block;
+mod deny_untransformed;
 mod expr;
+mod tracked;
 mod visitor;
 
 /// Defines a block of code where all mathematical operations are performed using checked methods.
@@ -32,6 +36,33 @@ pub fn checked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand::<Checked>(input)
 }
 
+/// Like [`checked!`], but applied as an attribute on an entire `fn`, `impl`, or `mod`
+/// item, rewriting every arithmetic expression in its body instead of requiring it to
+/// be wrapped in a macro invocation.
+///
+/// Named `checked_fn` rather than `checked` because a function-like macro and an
+/// attribute macro cannot share a name within the same proc-macro crate.
+///
+/// `default! { .. }` still works as an escape hatch inside the annotated item.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::checked_fn;
+///
+/// #[checked_fn]
+/// fn add(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn checked_fn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_item::<Checked>(attr, item)
+}
+
 /// Defines a block of code where all mathematical operations use overflowing methods.
 ///
 /// When an operation overflows, it will not panic; instead, it will return the result of the operation, wrapping around if necessary.
@@ -53,9 +84,38 @@ pub fn overflowing(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand::<Overflowing>(input)
 }
 
+/// Like [`overflowing!`], but applied as an attribute on an entire `fn`, `impl`, or `mod`
+/// item, rewriting every arithmetic expression in its body instead of requiring it to
+/// be wrapped in a macro invocation.
+///
+/// Named `overflowing_fn` rather than `overflowing` because a function-like macro and
+/// an attribute macro cannot share a name within the same proc-macro crate.
+///
+/// `default! { .. }` still works as an escape hatch inside the annotated item.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::overflowing_fn;
+///
+/// #[overflowing_fn]
+/// fn add(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn overflowing_fn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_item::<Overflowing>(attr, item)
+}
+
 /// Defines a block of code where all mathematical operations use saturating methods.
 ///
 /// When an operation would overflow, it will instead return the maximum (or minimum) value of the type.
+/// Note that division and remainder still panic on a zero divisor, just like `/` and `%` do by
+/// default; saturation only applies to the `MIN / -1` overflow case.
 ///
 /// # Example
 ///
@@ -74,6 +134,33 @@ pub fn saturating(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand::<Saturating>(input)
 }
 
+/// Like [`saturating!`], but applied as an attribute on an entire `fn`, `impl`, or `mod`
+/// item, rewriting every arithmetic expression in its body instead of requiring it to
+/// be wrapped in a macro invocation.
+///
+/// Named `saturating_fn` rather than `saturating` because a function-like macro and an
+/// attribute macro cannot share a name within the same proc-macro crate.
+///
+/// `default! { .. }` still works as an escape hatch inside the annotated item.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::saturating_fn;
+///
+/// #[saturating_fn]
+/// fn add(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn saturating_fn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_item::<Saturating>(attr, item)
+}
+
 /// Defines a block of code where all mathematical operations use checked methods.
 /// If any operation results in an overflow, it will return `None`, propagating the error using the `?` operator.
 ///
@@ -102,6 +189,199 @@ pub fn propagating(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand::<Propagating>(input)
 }
 
+/// Like [`propagating!`], but applied as an attribute on an entire `fn`, `impl`, or `mod`
+/// item, rewriting every arithmetic expression in its body instead of requiring it to
+/// be wrapped in a macro invocation.
+///
+/// Named `propagating_fn` rather than `propagating` because a function-like macro and
+/// an attribute macro cannot share a name within the same proc-macro crate.
+///
+/// `default! { .. }` still works as an escape hatch inside the annotated item.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::propagating_fn;
+///
+/// #[propagating_fn]
+/// fn add(a: usize, b: usize) -> Option<usize> {
+///     Some(a + b)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn propagating_fn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_item::<Propagating>(attr, item)
+}
+
+/// Defines a block of code where all mathematical operations use `strict_*` methods.
+///
+/// Like `checked!`, this always panics on overflow, even in release builds compiled with
+/// `-C overflow-checks=off`; unlike `checked!`, which relies on `checked_*` plus
+/// `.expect(..)`, it calls the `strict_*` methods directly.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::strict;
+///
+/// fn main() {
+///     strict! {
+///         let a = 10usize + 5usize;
+///         let b = 20usize - 10usize;
+///         let c = 3usize * 7usize;
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn strict(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand::<Strict>(input)
+}
+
+/// Like [`strict!`], but applied as an attribute on an entire `fn`, `impl`, or `mod`
+/// item, rewriting every arithmetic expression in its body instead of requiring it to
+/// be wrapped in a macro invocation.
+///
+/// Named `strict_fn` rather than `strict` because a function-like macro and an
+/// attribute macro cannot share a name within the same proc-macro crate.
+///
+/// `default! { .. }` still works as an escape hatch inside the annotated item.
+///
+/// # Example
+///
+/// ```rust
+/// use overf::strict_fn;
+///
+/// #[strict_fn]
+/// fn add(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn strict_fn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    expand_item::<Strict>(attr, item)
+}
+
+/// Defines a block of code where arithmetic expressions produce a monadic `Checked<T>`
+/// wrapper instead of rewriting each operation individually.
+///
+/// Every `+`, `-`, `*`, `/` is left as a normal operator, but its literal or
+/// bare-identifier operands are wrapped in `Checked::new(..)`; the `std::ops` impls
+/// generated for this expansion poison the whole chain to `None` the moment one step
+/// overflows or divides by zero (or computes `MIN / -1`). Call `.check()` once, on the
+/// final value, to turn the chain into an `Option<T>`.
+///
+/// `Checked<T>` only exists within the expansion of this macro (a `proc-macro` crate
+/// can't export a plain type), so it can't be named outside a `tracked!` block, and only
+/// literal/bare-identifier operands of an arithmetic operator are wrapped automatically;
+/// anything else (a function call, an already-`Checked` sub-expression, ...) is passed
+/// through as-is. A single invocation generates one `Checked<T>` type shared by every
+/// statement in its block, so a `let`-bound `Checked<T>` value from an earlier statement
+/// flows into a later one unwrapped, instead of being wrapped again.
+///
+/// # Limitation: non-leaf operands must already be `Checked<T>`
+///
+/// Because the rewrite has no type information, it can't tell whether a non-leaf
+/// operand (a function call, a float literal, ...) already evaluates to `Checked<T>` or
+/// to a plain number; it always assumes the former and leaves it untouched. Writing
+/// `let x = foo() * bar();` where `foo`/`bar` return plain `i32`s compiles as ordinary
+/// `i32` multiplication, but `x` is recorded as `Checked` for a later statement to reuse
+/// unwrapped, so that later use fails with a confusing `i32 + Checked<i32>`-style type
+/// error instead of a clear one. Bind such a value to a variable with its own `let`
+/// statement first, so it's a bare identifier by the time it reaches the arithmetic:
+///
+/// ```rust,compile_fail
+/// use overf::tracked;
+///
+/// fn foo() -> i32 { 1 }
+/// fn bar() -> i32 { 2 }
+///
+/// tracked! {
+///     // `foo()`/`bar()` return plain `i32`s, not `Checked<i32>`, so this line alone
+///     // compiles as ordinary `i32` multiplication...
+///     let x = foo() * bar();
+///     // ...but `x` still gets recorded as if it held a `Checked<i32>`, so using it
+///     // here fails: `1` is wrapped into `Checked::new(1)` while `x` is left bare.
+///     let y = x + 1;
+/// }
+/// ```
+///
+/// ```rust
+/// use overf::tracked;
+///
+/// fn foo() -> i32 { 1 }
+/// fn bar() -> i32 { 2 }
+///
+/// tracked! {
+///     let f = foo();
+///     let g = bar();
+///     let x = f * g; // `f`/`g` are bare identifiers, so they're wrapped automatically
+/// }
+///
+/// assert_eq!(x.check(), Some(2));
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use overf::tracked;
+///
+/// fn main() {
+///     let a = 10i32;
+///     let b = 2i32;
+///
+///     tracked! {
+///         let x = a + b;
+///         let result = ((x + 2) / 3 + 5) * b + 1;
+///     }
+///
+///     assert_eq!(result.check(), Some(19));
+/// }
+/// ```
+#[proc_macro]
+pub fn tracked(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    tracked::expand(input)
+}
+
+/// Denies arithmetic operators hidden inside another macro's token stream, instead of
+/// silently leaving them with Rust's default overflow behavior.
+///
+/// `syn` can't parse an arbitrary macro's arguments as expressions, so e.g.
+/// `println!("{}", a + b)` has an `a + b` that is invisible to `MathBlockVisitor` and
+/// never gets rewritten by any of this crate's modes. This attribute only scans for that
+/// one case (arithmetic inside a macro invocation's tokens); it does not attempt to
+/// catch every syntactic position a mode's visitor happens to skip.
+///
+/// Stack this *outside* one of the mode attributes so it inspects the original source
+/// before that attribute rewrites anything:
+///
+/// ```rust
+/// use overf::{checked_fn, deny_untransformed};
+///
+/// #[deny_untransformed]
+/// #[checked_fn]
+/// fn add(a: usize, b: usize) -> usize {
+///     a + b
+/// }
+/// ```
+///
+/// This is a token-level heuristic, not a full parse: it can't distinguish a unary `*`
+/// dereference from a multiplication, so it may also flag a dereference inside a macro
+/// call. `default! { .. }` bodies are still exempt, since that's an intentional escape
+/// hatch rather than a silently-skipped operation.
+#[proc_macro_attribute]
+pub fn deny_untransformed(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    deny_untransformed::expand_item(attr, item)
+}
+
 /// Resets the overflow behavior to the default behavior of Rust.
 ///
 /// This is useful when you want to exit a block with custom overflow handling and revert to the standard behavior.
@@ -0,0 +1,210 @@
+use std::{
+    collections::HashSet,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote,
+    visit_mut::{self, VisitMut},
+    BinOp, Block, Expr, ExprBinary, ExprLit, ExprPath, Ident, Lit, Local, Pat,
+};
+
+use crate::visitor::is_default_macro;
+
+/// Integer types the generated `Checked<T>` arithmetic impls are emitted for.
+const INT_TYPES: &[&str] = &[
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+];
+
+/// Counter used to give each `tracked!` expansion its own uniquely-named `Checked<T>`
+/// type, so that two invocations sharing a scope don't both try to define a type named
+/// the same thing and collide.
+static INVOCATION: AtomicUsize = AtomicUsize::new(0);
+
+/// Expands a `tracked! { .. }` block.
+///
+/// Unlike the other modes, `tracked!` doesn't rewrite operators into method calls.
+/// Instead it wraps literal and bare-identifier operands of `+`, `-`, `*`, `/` in
+/// `Checked::new(..)`, and prepends a small `Checked<T>` type whose `std::ops` impls
+/// poison the whole chain to `None` the moment one step overflows or divides by zero
+/// (or computes `MIN / -1`).
+///
+/// `Checked<T>` is generated as part of the expansion, local to the block it's used in,
+/// rather than exported as a regular library item, because a `proc-macro` crate cannot
+/// export anything other than macros.
+pub(crate) fn expand(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input2 = proc_macro2::TokenStream::from(input);
+    let input = proc_macro::TokenStream::from(quote! { { #input2 } });
+    let block = syn::parse_macro_input!(input as Block);
+    match try_expand(block) {
+        Ok(res) => res.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn try_expand(mut block: Block) -> syn::Result<TokenStream> {
+    let checked_ty = format_ident!(
+        "__OverfChecked{}",
+        INVOCATION.fetch_add(1, Ordering::Relaxed)
+    );
+
+    let mut visitor = TrackedVisitor::new(checked_ty.clone());
+    visitor.visit_block_mut(&mut block);
+
+    // One `Checked<T>` definition for the whole invocation, uniquely named so several
+    // `tracked!` invocations can share a scope, rather than one private copy per
+    // statement: the latter gave each statement's `Checked` its own distinct (if
+    // identically-named) type, so a binding already holding a `Checked<T>` value got
+    // rewrapped into `Checked<Checked<T>>` the moment it was used in a later statement.
+    let prelude = checked_type(&checked_ty);
+    let mut res = quote! { #prelude };
+    for stmt in block.stmts {
+        res.extend(quote! { #stmt });
+    }
+    Ok(res)
+}
+
+/// Generates the local `Checked<T>` type and its `checked_*`-backed arithmetic impls,
+/// named `ident` so each `tracked!` invocation can have its own without colliding with
+/// another invocation sharing the same scope.
+fn checked_type(ident: &Ident) -> TokenStream {
+    let ops = INT_TYPES.iter().map(|ty| {
+        let ty = syn::Ident::new(ty, Span::call_site());
+        quote! {
+            impl ::core::ops::Add for #ident<#ty> {
+                type Output = Self;
+                fn add(self, rhs: Self) -> Self {
+                    #ident(self.0.and_then(|a| rhs.0.and_then(|b| a.checked_add(b))))
+                }
+            }
+
+            impl ::core::ops::Sub for #ident<#ty> {
+                type Output = Self;
+                fn sub(self, rhs: Self) -> Self {
+                    #ident(self.0.and_then(|a| rhs.0.and_then(|b| a.checked_sub(b))))
+                }
+            }
+
+            impl ::core::ops::Mul for #ident<#ty> {
+                type Output = Self;
+                fn mul(self, rhs: Self) -> Self {
+                    #ident(self.0.and_then(|a| rhs.0.and_then(|b| a.checked_mul(b))))
+                }
+            }
+
+            impl ::core::ops::Div for #ident<#ty> {
+                type Output = Self;
+                fn div(self, rhs: Self) -> Self {
+                    #ident(self.0.and_then(|a| rhs.0.and_then(|b| a.checked_div(b))))
+                }
+            }
+        }
+    });
+
+    quote! {
+        #[derive(Clone, Copy)]
+        struct #ident<T>(Option<T>);
+
+        impl<T> #ident<T> {
+            fn new(value: T) -> Self {
+                #ident(Some(value))
+            }
+
+            fn check(self) -> Option<T> {
+                self.0
+            }
+        }
+
+        #(#ops)*
+    }
+}
+
+/// Wraps the literal/bare-identifier operands of `+`, `-`, `*`, `/` in `Checked::new(..)`
+/// so they type-check against the generated `Checked<T>`, while keeping track of which
+/// `let`-bound identifiers already evaluate to a `Checked<T>` value so a later statement
+/// can use them bare instead of wrapping them again.
+struct TrackedVisitor {
+    checked_ty: Ident,
+    /// Identifiers bound, earlier in the same invocation, to an expression that
+    /// evaluates to `Checked<T>` rather than a plain integer.
+    checked_idents: HashSet<Ident>,
+}
+
+impl TrackedVisitor {
+    fn new(checked_ty: Ident) -> Self {
+        Self {
+            checked_ty,
+            checked_idents: HashSet::new(),
+        }
+    }
+
+    /// Whether `expr` is already known to evaluate to a `Checked<T>` value: a path
+    /// previously recorded in `checked_idents`, or (once its operands have been
+    /// wrapped) a top-level tracked binary expression.
+    fn produces_checked(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Path(ExprPath { qself: None, path, .. }) => path
+                .get_ident()
+                .is_some_and(|ident| self.checked_idents.contains(ident)),
+            Expr::Binary(ExprBinary { op, .. }) => is_tracked_op(op),
+            Expr::Paren(paren) => self.produces_checked(&paren.expr),
+            Expr::Group(group) => self.produces_checked(&group.expr),
+            _ => false,
+        }
+    }
+
+    /// Wraps `expr` in `Checked::new(..)` if it's a bare integer literal, or a bare
+    /// identifier not already known to hold a `Checked<T>` value; leaves anything else
+    /// (function calls, indexing, an identifier in `checked_idents`, ...) alone, since
+    /// it's expected to already evaluate to `Checked<T>`.
+    fn wrap_operand(&self, expr: &mut Expr) {
+        if self.is_trackable_leaf(expr) {
+            let checked_ty = &self.checked_ty;
+            let inner = expr.clone();
+            *expr = parse_quote! { #checked_ty::new(#inner) };
+        }
+    }
+
+    fn is_trackable_leaf(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Lit(ExprLit { lit: Lit::Int(_), .. }) => true,
+            Expr::Path(ExprPath { qself: None, path, .. }) => path
+                .get_ident()
+                .is_some_and(|ident| !self.checked_idents.contains(ident)),
+            _ => false,
+        }
+    }
+}
+
+impl VisitMut for TrackedVisitor {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if is_default_macro(node) {
+            return;
+        }
+
+        visit_mut::visit_expr_mut(self, node);
+
+        if let Expr::Binary(ExprBinary { left, op, right, .. }) = node {
+            if is_tracked_op(op) {
+                self.wrap_operand(left);
+                self.wrap_operand(right);
+            }
+        }
+    }
+
+    fn visit_local_mut(&mut self, local: &mut Local) {
+        visit_mut::visit_local_mut(self, local);
+
+        if let (Pat::Ident(pat_ident), Some(init)) = (&local.pat, &local.init) {
+            if self.produces_checked(&init.expr) {
+                self.checked_idents.insert(pat_ident.ident.clone());
+            }
+        }
+    }
+}
+
+fn is_tracked_op(op: &BinOp) -> bool {
+    matches!(op, BinOp::Add(_) | BinOp::Sub(_) | BinOp::Mul(_) | BinOp::Div(_))
+}
@@ -0,0 +1,59 @@
+use std::marker::PhantomData;
+
+use syn::{
+    visit_mut::{self, VisitMut},
+    Expr, ExprBinary, ExprUnary,
+};
+
+use crate::{block::MathBlock, expr};
+
+/// Walks a syntax tree and rewrites arithmetic expressions according to `B`.
+///
+/// Recurses into every expression except inside a `default! { .. }` macro
+/// invocation, which is left untouched so users can opt a sub-expression back
+/// into the standard library's default overflow behavior.
+pub(crate) struct MathBlockVisitor<B> {
+    _mode: PhantomData<B>,
+}
+
+impl<B: MathBlock> MathBlockVisitor<B> {
+    pub(crate) fn new() -> Self {
+        Self { _mode: PhantomData }
+    }
+}
+
+impl<B: MathBlock> VisitMut for MathBlockVisitor<B> {
+    fn visit_expr_mut(&mut self, node: &mut Expr) {
+        if is_default_macro(node) {
+            return;
+        }
+
+        visit_mut::visit_expr_mut(self, node);
+
+        let rewritten = match node {
+            Expr::Binary(ExprBinary { left, op, right, .. }) => {
+                if let Some(op) = expr::bin_op(*op) {
+                    expr::rewrite_binary::<B>(op, (**left).clone(), (**right).clone())
+                } else if let Some(op) = expr::compound_assign_op(*op) {
+                    expr::rewrite_compound_assign::<B>(op, (**left).clone(), (**right).clone())
+                } else {
+                    None
+                }
+            }
+            Expr::Unary(ExprUnary { op, expr, .. }) => {
+                expr::unary_op(*op).and_then(|op| expr::rewrite_unary::<B>(op, (**expr).clone()))
+            }
+            _ => None,
+        };
+
+        if let Some(rewritten) = rewritten {
+            *node = rewritten;
+        }
+    }
+}
+
+/// Whether `node` is a `default! { .. }` invocation, which acts as an escape
+/// hatch and must not be recursed into.
+pub(crate) fn is_default_macro(node: &Expr) -> bool {
+    matches!(node, Expr::Macro(m) if m.mac.path.is_ident("default"))
+}
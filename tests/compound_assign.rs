@@ -0,0 +1,35 @@
+//! Regression coverage for `rewrite_compound_assign` (`src/expr.rs`): the lvalue of a
+//! compound assignment must be evaluated exactly once, even when it's a side-effecting
+//! place expression like an indexing call.
+
+use overf::checked;
+
+#[test]
+fn compound_assign_evaluates_the_index_expression_once() {
+    let mut calls = 0;
+    let mut v = [0i32; 4];
+
+    let mut idx = || {
+        calls += 1;
+        1usize
+    };
+
+    checked! {
+        v[idx()] += 10;
+    }
+
+    assert_eq!(v[1], 10);
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn checked_compound_assign_panics_on_overflow() {
+    let result = std::panic::catch_unwind(|| {
+        let mut x = u8::MAX;
+        checked! {
+            x += 1;
+        }
+        x
+    });
+    assert!(result.is_err());
+}
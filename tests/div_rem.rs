@@ -0,0 +1,75 @@
+//! Regression coverage for the per-mode division semantics of
+//! `checked!`/`overflowing!`/`saturating!`/`propagating!`: `checked!`, `overflowing!`
+//! and `saturating!` panic on a zero divisor just like plain `/` does, `propagating!`
+//! returns `None` instead, and the `MIN / -1` overflow case is handled per-mode.
+
+use overf::{checked, overflowing, propagating, saturating};
+
+#[test]
+fn checked_div_by_zero_panics() {
+    let result = std::panic::catch_unwind(|| {
+        let zero = 0usize;
+        checked! {
+            let x = 10usize / zero;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn overflowing_div_by_zero_panics() {
+    let result = std::panic::catch_unwind(|| {
+        let zero = 0usize;
+        overflowing! {
+            let x = 10usize / zero;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn saturating_div_by_zero_panics() {
+    let result = std::panic::catch_unwind(|| {
+        let zero = 0usize;
+        saturating! {
+            let x = 10usize / zero;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn saturating_min_div_minus_one_saturates_to_max() {
+    saturating! {
+        let x = i32::MIN / -1i32;
+    }
+    assert_eq!(x, i32::MAX);
+}
+
+#[test]
+fn propagating_div_by_zero_returns_none() {
+    fn example() -> Option<usize> {
+        let zero = 0usize;
+        propagating! {
+            let x = 10usize / zero;
+            Some(x)
+        }
+    }
+
+    assert_eq!(example(), None);
+}
+
+#[test]
+fn propagating_min_div_minus_one_returns_none() {
+    fn example() -> Option<i32> {
+        propagating! {
+            let x = i32::MIN / -1i32;
+            Some(x)
+        }
+    }
+
+    assert_eq!(example(), None);
+}
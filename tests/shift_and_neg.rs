@@ -0,0 +1,76 @@
+//! Regression coverage for the shift and unary-negation rewrites added alongside
+//! compound assignment (`rewrite_binary`/`rewrite_unary` in `src/expr.rs`).
+
+use overf::{checked, overflowing, saturating, strict};
+
+#[test]
+fn checked_shift_panics_on_out_of_range_amount() {
+    let result = std::panic::catch_unwind(|| {
+        checked! {
+            let x = 1u8 << 8;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn strict_shift_panics_on_out_of_range_amount() {
+    let result = std::panic::catch_unwind(|| {
+        strict! {
+            let x = 1u8 << 8;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn checked_shift_accepts_a_non_u32_shift_amount() {
+    // `<<`/`>>` accept a right-hand side of any integer type; a bare literal like
+    // `1u8 << 8` infers straight to `u32` and doesn't exercise the cast that makes
+    // `rewrite_binary` support a non-`u32` shift amount like this `usize`.
+    let s: usize = 3;
+
+    checked! {
+        let x = 1u8 << s;
+    }
+    assert_eq!(x, 8);
+}
+
+#[test]
+fn overflowing_shift_masks_the_shift_amount() {
+    overflowing! {
+        // `std`'s `overflowing_shl` masks the shift amount to the bit width instead of
+        // refusing it, so shifting by 9 on a `u8` behaves like shifting by `9 % 8 == 1`.
+        let x = 1u8 << 9;
+    }
+    assert_eq!(x, 2);
+}
+
+#[test]
+fn checked_neg_of_min_panics() {
+    let result = std::panic::catch_unwind(|| {
+        checked! {
+            let x = -i32::MIN;
+            x
+        }
+    });
+    assert!(result.is_err());
+}
+
+#[test]
+fn overflowing_neg_of_min_wraps_to_itself() {
+    overflowing! {
+        let x = -i32::MIN;
+    }
+    assert_eq!(x, i32::MIN);
+}
+
+#[test]
+fn saturating_neg_of_min_saturates_to_max() {
+    saturating! {
+        let x = -i32::MIN;
+    }
+    assert_eq!(x, i32::MAX);
+}
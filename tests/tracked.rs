@@ -0,0 +1,55 @@
+//! Regression coverage for `tracked!`'s `Checked<T>` chain (`src/tracked.rs`): values
+//! compose across statements within one invocation, the chain poisons to `None` on
+//! overflow or division by zero, and two invocations can share a scope.
+
+use overf::tracked;
+
+#[test]
+fn composes_across_statements() {
+    let a = 10i32;
+    let b = 2i32;
+
+    tracked! {
+        let x = a + b;
+        let result = ((x + 2) / 3 + 5) * b + 1;
+    }
+
+    assert_eq!(result.check(), Some(19));
+}
+
+#[test]
+fn poisons_the_whole_chain_on_overflow() {
+    let a = i32::MAX;
+
+    tracked! {
+        let x = a + 1;
+        let result = x + 1;
+    }
+
+    assert_eq!(result.check(), None);
+}
+
+#[test]
+fn poisons_the_whole_chain_on_division_by_zero() {
+    let zero = 0i32;
+
+    tracked! {
+        let x = 10i32 / zero;
+        let result = x + 1;
+    }
+
+    assert_eq!(result.check(), None);
+}
+
+#[test]
+fn two_invocations_can_share_a_scope() {
+    tracked! {
+        let a = 1i32 + 2i32;
+    }
+    tracked! {
+        let b = 3i32 + 4i32;
+    }
+
+    assert_eq!(a.check(), Some(3));
+    assert_eq!(b.check(), Some(7));
+}